@@ -0,0 +1,94 @@
+use bevy::prelude::*;
+
+use super::{ClientSet, RepliconClient};
+
+/// Adds [`ClientStats`] and keeps its per-channel counters updated from [`RepliconClient`].
+pub struct ClientDiagnosticsPlugin;
+
+impl Plugin for ClientDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ClientStats>().add_systems(
+            PreUpdate,
+            update_channel_stats.in_set(ClientSet::ReceivePackets),
+        );
+    }
+}
+
+/// Replication traffic counters, added by [`ClientDiagnosticsPlugin`], which updates
+/// [`Self::channels`] every tick.
+///
+/// `entities_changed`, `components_changed`, `mappings`, `despawns`, `packets` and `bytes` are
+/// aggregate counters meant to be populated by the replication receiving systems as updates are
+/// applied. `channels` breaks traffic down per channel ID instead, so a noisy user-defined channel
+/// can be told apart from replication traffic; it is populated by [`update_channel_stats`]
+/// regardless of what (if anything) feeds the aggregate counters.
+#[derive(Resource, Default, Debug)]
+pub struct ClientStats {
+    /// Entities changed (spawned, updated or despawned) by replication.
+    pub entities_changed: u32,
+    /// Components changed by replication.
+    pub components_changed: u32,
+    /// Entity mappings received from the server.
+    pub mappings: u32,
+    /// Entity despawns received from the server.
+    pub despawns: u32,
+    /// Packets received from the server.
+    pub packets: u32,
+    /// Bytes received from the server.
+    pub bytes: u64,
+    /// Per-channel breakdown, indexed by channel ID.
+    pub channels: Vec<ChannelStats>,
+}
+
+/// Per-channel traffic counters, part of [`ClientStats::channels`].
+#[derive(Default, Debug, Clone, Copy)]
+pub struct ChannelStats {
+    /// Messages received on this channel.
+    pub messages: u32,
+    /// Bytes received on this channel.
+    pub bytes: u64,
+    /// Rolling estimate of bytes received per second on this channel.
+    pub bytes_per_sec: f32,
+}
+
+impl ChannelStats {
+    /// Folds a tick's worth of received messages into the counters, decaying `bytes_per_sec`
+    /// towards the tick's rate with an exponential moving average so a single quiet or noisy tick
+    /// doesn't make the graphed rate jump around. `messages`/`bytes` may be zero for an idle tick,
+    /// which is how the rate decays back down once a channel goes quiet.
+    fn record(&mut self, messages: u32, bytes: u64, delta: f32) {
+        self.messages += messages;
+        self.bytes += bytes;
+
+        if delta <= f32::EPSILON {
+            return;
+        }
+
+        const SMOOTHING: f32 = 0.1;
+        let instantaneous_rate = bytes as f32 / delta;
+        self.bytes_per_sec += (instantaneous_rate - self.bytes_per_sec) * SMOOTHING;
+    }
+}
+
+/// Updates [`ClientStats::channels`] from messages buffered in [`RepliconClient`].
+///
+/// Reads messages before the replication systems pop them later in the same tick, mirroring
+/// [`RepliconClient::received_messages`]'s channel layout.
+fn update_channel_stats(
+    time: Res<Time>,
+    client: Res<RepliconClient>,
+    mut stats: ResMut<ClientStats>,
+) {
+    let received = client.received_messages();
+    if stats.channels.len() < received.len() {
+        stats
+            .channels
+            .resize_with(received.len(), ChannelStats::default);
+    }
+
+    let delta = time.delta_seconds();
+    for (channel_id, messages) in received.iter().enumerate() {
+        let bytes = messages.iter().map(|message| message.len() as u64).sum();
+        stats.channels[channel_id].record(messages.len() as u32, bytes, delta);
+    }
+}
@@ -0,0 +1,233 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use super::{RepliconClient, RepliconClientStatus};
+
+/// Reserved channel used for heartbeat keep-alive messages.
+///
+/// The messaging backend must reserve this channel alongside the replication and user-defined
+/// channels, and echo back any payload received on it unchanged so the client can measure RTT.
+pub const HEARTBEAT_CHANNEL_ID: u8 = u8::MAX;
+
+/// Adds a heartbeat keep-alive subsystem that detects a silently dead connection and measures
+/// round-trip time.
+///
+/// Periodically sends a tiny keep-alive message over [`HEARTBEAT_CHANNEL_ID`] and expects it
+/// echoed back by the server, updating [`NetworkStats`] with the measured RTT. If no traffic at
+/// all (heartbeats or otherwise) is received within [`HeartbeatConfig::timeout`], the client is
+/// moved to [`RepliconClientStatus::Reconnecting`] on its own behalf.
+pub struct HeartbeatPlugin {
+    pub config: HeartbeatConfig,
+}
+
+impl Default for HeartbeatPlugin {
+    fn default() -> Self {
+        Self {
+            config: HeartbeatConfig::default(),
+        }
+    }
+}
+
+impl Plugin for HeartbeatPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(self.config)
+            .init_resource::<NetworkStats>()
+            .init_resource::<HeartbeatState>()
+            .add_systems(
+                PreUpdate,
+                (
+                    reserve_heartbeat_channel,
+                    reset_on_connect,
+                    send_heartbeats,
+                    receive_heartbeats,
+                    check_timeout,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Configures the heartbeat interval and connection timeout used by [`HeartbeatPlugin`].
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct HeartbeatConfig {
+    /// How often a keep-alive message is sent while connected.
+    pub interval: Duration,
+    /// How long to wait without receiving any traffic before considering the connection dead.
+    pub timeout: Duration,
+}
+
+impl Default for HeartbeatConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(1),
+            timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+/// Rolling connection-quality estimate derived from heartbeat round-trips.
+///
+/// Read this to show connection quality to players or to feed the diagnostics plugin.
+#[derive(Resource, Default, Debug)]
+pub struct NetworkStats {
+    rtt: Option<Duration>,
+    jitter: Duration,
+    last_received_at: Option<Duration>,
+}
+
+impl NetworkStats {
+    /// Returns the smoothed round-trip time, or `None` until the first heartbeat reply arrives.
+    #[inline]
+    pub fn rtt(&self) -> Option<Duration> {
+        self.rtt
+    }
+
+    /// Returns the smoothed jitter (mean deviation of RTT samples from the smoothed RTT).
+    #[inline]
+    pub fn jitter(&self) -> Duration {
+        self.jitter
+    }
+
+    /// Returns when any traffic was last received, in time since app startup.
+    #[inline]
+    pub fn last_received_at(&self) -> Option<Duration> {
+        self.last_received_at
+    }
+
+    /// Folds a new RTT sample into the rolling estimate using an exponential moving average,
+    /// mirroring the smoothing used by TCP (Jacobson/Karels).
+    fn record_sample(&mut self, sample: Duration) {
+        let sample_secs = sample.as_secs_f64();
+        match self.rtt {
+            Some(rtt) => {
+                let rtt_secs = rtt.as_secs_f64();
+                let error = sample_secs - rtt_secs;
+                self.rtt = Some(Duration::from_secs_f64((rtt_secs + error / 8.0).max(0.0)));
+
+                let jitter_secs = self.jitter.as_secs_f64();
+                self.jitter = Duration::from_secs_f64(
+                    (jitter_secs + (error.abs() - jitter_secs) / 4.0).max(0.0),
+                );
+            }
+            None => self.rtt = Some(sample),
+        }
+    }
+}
+
+/// Tracks in-flight heartbeats and when the connection was last known to be alive.
+#[derive(Resource, Default)]
+struct HeartbeatState {
+    next_sequence: u32,
+    pending: Vec<(u32, Duration)>,
+    last_sent_at: Duration,
+    last_received_tick: u64,
+    last_activity_at: Duration,
+    was_connected: bool,
+}
+
+/// Reserves [`HEARTBEAT_CHANNEL_ID`] in [`RepliconClient`]'s receive storage.
+///
+/// [`RepliconClient::setup_server_channels`] sizes that storage to the real, much smaller server
+/// channel count, so without this, any [`RepliconClient::receive`] or
+/// [`RepliconClient::insert_received`] call on [`HEARTBEAT_CHANNEL_ID`] would panic with an
+/// out-of-bounds channel ID. Runs every tick (not just once) since it only ever grows the storage,
+/// so it's safe regardless of when the real channel count is (re)configured.
+fn reserve_heartbeat_channel(mut client: ResMut<RepliconClient>) {
+    client.reserve_channel(HEARTBEAT_CHANNEL_ID);
+}
+
+/// Rebaselines [`HeartbeatState`] on the edge into [`RepliconClientStatus::Connected`].
+///
+/// Without this, a brand-new connection (or one that just survived a reconnect) inherits
+/// whatever `last_activity_at`/`last_received_tick` were left over from before the client was
+/// connected — zeroed defaults on the very first connection, or a stale pre-outage value after a
+/// reconnect. Either way [`check_timeout`] would see an apparent activity gap at least as long as
+/// the app has been running (or the outage lasted), and immediately flip the fresh connection
+/// back to [`RepliconClientStatus::Reconnecting`].
+fn reset_on_connect(
+    time: Res<Time>,
+    mut state: ResMut<HeartbeatState>,
+    client: Res<RepliconClient>,
+) {
+    let is_connected = client.is_connected();
+    if is_connected && !state.was_connected {
+        state.last_activity_at = time.elapsed();
+        state.last_received_tick = client.received_tick();
+        state.pending.clear();
+    }
+    state.was_connected = is_connected;
+}
+
+fn send_heartbeats(
+    time: Res<Time>,
+    config: Res<HeartbeatConfig>,
+    mut state: ResMut<HeartbeatState>,
+    mut client: ResMut<RepliconClient>,
+) {
+    if !client.is_connected() {
+        return;
+    }
+
+    let now = time.elapsed();
+    if now.saturating_sub(state.last_sent_at) < config.interval {
+        return;
+    }
+    state.last_sent_at = now;
+
+    let sequence = state.next_sequence;
+    state.next_sequence = state.next_sequence.wrapping_add(1);
+    state.pending.push((sequence, now));
+
+    // Heartbeats are best-effort: if the channel is out of credits, skip this beat rather than
+    // competing with real traffic for the budget.
+    let _ = client.send(HEARTBEAT_CHANNEL_ID, sequence.to_le_bytes().to_vec());
+}
+
+fn receive_heartbeats(
+    time: Res<Time>,
+    mut state: ResMut<HeartbeatState>,
+    mut stats: ResMut<NetworkStats>,
+    mut client: ResMut<RepliconClient>,
+) {
+    if !client.is_connected() {
+        return;
+    }
+
+    let now = time.elapsed();
+    while let Some(message) = client.receive(HEARTBEAT_CHANNEL_ID) {
+        let Ok(sequence_bytes) = message.as_ref().try_into() else {
+            warn!("received malformed heartbeat reply");
+            continue;
+        };
+        let sequence = u32::from_le_bytes(sequence_bytes);
+        if let Some(index) = state.pending.iter().position(|&(seq, _)| seq == sequence) {
+            let (_, sent_at) = state.pending.remove(index);
+            stats.record_sample(now.saturating_sub(sent_at));
+        }
+    }
+
+    if client.received_tick() != state.last_received_tick {
+        state.last_received_tick = client.received_tick();
+        state.last_activity_at = now;
+        stats.last_received_at = Some(now);
+    }
+}
+
+fn check_timeout(
+    time: Res<Time>,
+    config: Res<HeartbeatConfig>,
+    state: Res<HeartbeatState>,
+    mut client: ResMut<RepliconClient>,
+) {
+    if !client.is_connected() {
+        return;
+    }
+
+    if time.elapsed().saturating_sub(state.last_activity_at) > config.timeout {
+        debug!("no traffic received within the heartbeat timeout, reconnecting");
+        client.set_status(RepliconClientStatus::Reconnecting {
+            client_id: client.id(),
+        });
+    }
+}
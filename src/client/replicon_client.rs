@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 use bytes::Bytes;
 
+use super::ClientSet;
 use crate::core::ClientId;
 
 /// Stores information about a client independent from the messaging backend.
@@ -14,6 +15,11 @@ use crate::core::ClientId;
 /// - For sending messages, [`Self::drain_sent`] should be used to drain all sent messages.
 /// A system to forward Replicon messages to the backend should run in
 /// [`ClientSet::SendPackets`](super::ClientSet::SendPackets).
+///
+/// When the backend reports a transient drop via [`RepliconClientStatus::Reconnecting`], buffered
+/// sent messages are kept (up to [`Self::set_reconnect_policy`]'s limits) instead of being
+/// discarded, so they can be flushed once the session resumes. See [`Self::set_status`] for
+/// details on when the buffer is flushed versus dropped.
 #[derive(Resource, Default)]
 pub struct RepliconClient {
     /// Client connection status.
@@ -27,6 +33,33 @@ pub struct RepliconClient {
 
     /// List of sent messages and their channels since the last tick.
     sent_messages: Vec<(u8, Bytes)>,
+
+    /// Limits applied to buffered sent messages while [`RepliconClientStatus::Reconnecting`].
+    reconnect_policy: ReconnectPolicy,
+
+    /// Session events produced by [`Self::set_status`] since the last drain.
+    ///
+    /// Drained and re-emitted as [`SessionEvent`] by a system that should run in
+    /// [`ClientSet::ReceivePackets`](super::ClientSet::ReceivePackets).
+    session_events: Vec<SessionEvent>,
+
+    /// Incremented every time a message arrives via [`Self::insert_received`].
+    ///
+    /// Used by [`heartbeat`](super::heartbeat) to detect that the connection is still alive
+    /// without caring which channel the traffic came in on.
+    received_tick: u64,
+
+    /// Available send credits per channel, indexed by channel ID.
+    ///
+    /// A missing entry (the index is out of bounds) means the channel has no configured limit.
+    /// See [`Self::set_channel_credits`].
+    channel_credits: Vec<Option<usize>>,
+
+    /// Disconnect reasons produced by [`Self::set_status`] since the last drain.
+    ///
+    /// Drained and re-emitted as [`DisconnectReason`] by a system that should run in
+    /// [`ClientSet::ReceivePackets`](super::ClientSet::ReceivePackets).
+    disconnect_events: Vec<DisconnectReason>,
 }
 
 impl RepliconClient {
@@ -35,6 +68,19 @@ impl RepliconClient {
         self.received_messages.resize(channels_count, Vec::new());
     }
 
+    /// Grows the receive messages storage so `channel_id` is in bounds, if it isn't already.
+    ///
+    /// Unlike [`Self::setup_server_channels`], this never shrinks the storage, so it's safe to
+    /// call on every tick from a fixed, out-of-band channel like
+    /// [`HEARTBEAT_CHANNEL_ID`](super::heartbeat::HEARTBEAT_CHANNEL_ID) without racing whichever
+    /// system sets up the real server channel count.
+    pub(super) fn reserve_channel(&mut self, channel_id: u8) {
+        let channel_id = channel_id as usize;
+        if self.received_messages.len() <= channel_id {
+            self.received_messages.resize(channel_id + 1, Vec::new());
+        }
+    }
+
     /// Pops the next available message from the server over a channel.
     pub fn receive<I: Into<u8>>(&mut self, channel_id: I) -> Option<Bytes> {
         if !self.is_connected() {
@@ -52,39 +98,215 @@ impl RepliconClient {
     }
 
     /// Sends a message to the server over a channel.
-    pub fn send<I: Into<u8>, B: Into<Bytes>>(&mut self, channel_id: I, message: B) {
+    ///
+    /// Consumes one credit from the channel's budget (see [`Self::set_channel_credits`]).
+    /// Returns [`SendError::WouldBlock`] instead of queueing the message if the channel has no
+    /// credits left, so callers can throttle or drop non-essential traffic under congestion
+    /// instead of growing the queue without bound. Channels with no configured limit always
+    /// succeed.
+    pub fn send<I: Into<u8>, B: Into<Bytes>>(
+        &mut self,
+        channel_id: I,
+        message: B,
+    ) -> Result<(), SendError> {
         if !self.is_connected() {
             warn!("trying to send a message when the client is not connected");
-            return;
+            return Ok(());
+        }
+
+        let channel_id = channel_id.into();
+        if let Some(credits) = self
+            .channel_credits
+            .get_mut(channel_id as usize)
+            .and_then(Option::as_mut)
+        {
+            if *credits == 0 {
+                return Err(SendError::WouldBlock);
+            }
+            *credits -= 1;
+        }
+
+        self.sent_messages.push((channel_id, message.into()));
+
+        Ok(())
+    }
+
+    /// Sets the credit budget for a channel, resizing the credit storage if necessary.
+    ///
+    /// Channels without a configured budget can send without limit. Call this again to adjust a
+    /// channel's budget, e.g. when the backend reports a change in real transport capacity.
+    pub fn set_channel_credits<I: Into<u8>>(&mut self, channel_id: I, credits: usize) {
+        let channel_id = channel_id.into() as usize;
+        if self.channel_credits.len() <= channel_id {
+            self.channel_credits.resize(channel_id + 1, None);
         }
 
-        self.sent_messages.push((channel_id.into(), message.into()));
+        self.channel_credits[channel_id] = Some(credits);
+    }
+
+    /// Grants additional credits to an already-configured channel.
+    ///
+    /// Does nothing if the channel has no configured budget (see [`Self::set_channel_credits`]).
+    pub fn grant_channel_credits<I: Into<u8>>(&mut self, channel_id: I, credits: usize) {
+        if let Some(Some(existing)) = self.channel_credits.get_mut(channel_id.into() as usize) {
+            *existing = existing.saturating_add(credits);
+        }
     }
 
     /// Sets the client connection status.
     ///
     /// Should be called only from the messaging backend when the client status changes.
-    /// Discards all messages if the state changes from [`RepliconClientStatus::Connected`].
+    /// Discards all messages if the state changes from [`RepliconClientStatus::Connected`] to
+    /// anything other than [`RepliconClientStatus::Reconnecting`].
+    ///
+    /// On a transition into [`RepliconClientStatus::Reconnecting`], received messages are
+    /// discarded, but buffered sent messages are kept (trimmed to [`Self::set_reconnect_policy`]'s limits) instead of being cleared, so they
+    /// can be replayed once the connection comes back. On the subsequent transition to
+    /// [`RepliconClientStatus::Connected`], the buffer is flushed to the backend if the server
+    /// handed back the same client ID (session resume) and pushes [`SessionEvent::Resumed`], or
+    /// discarded and pushes [`SessionEvent::New`] otherwise. If the reconnect attempt is abandoned
+    /// instead (any other status), the buffer is discarded. Reporting
+    /// [`RepliconClientStatus::Reconnecting`] again while already reconnecting (e.g. to update the
+    /// known client ID) is a no-op for the buffer, so repeated reports from the backend don't wipe
+    /// it out.
+    ///
+    /// On a transition into [`RepliconClientStatus::Disconnected`], pushes the carried
+    /// [`DisconnectReason`] so game code can react to it. Use a system reading
+    /// [`EventReader<SessionEvent>`] or [`EventReader<DisconnectReason>`] to react to these.
+    ///
     /// See also [`Self::status`].
     pub fn set_status(&mut self, status: RepliconClientStatus) {
         debug!("changing `RepliconClient` status to `{status:?}`");
 
-        if self.is_connected() && !matches!(status, RepliconClientStatus::Connected { .. }) {
-            for channel_messages in &mut self.received_messages {
-                channel_messages.clear();
+        let old_status = self.status.clone();
+        match (old_status, status.clone()) {
+            (RepliconClientStatus::Connected { .. }, RepliconClientStatus::Reconnecting { .. }) => {
+                for channel_messages in &mut self.received_messages {
+                    channel_messages.clear();
+                }
+                self.trim_buffered_sent();
+            }
+            (
+                RepliconClientStatus::Reconnecting { client_id: prev_id },
+                RepliconClientStatus::Connected { client_id },
+            ) => {
+                if prev_id.is_some() && prev_id == client_id {
+                    self.session_events.push(SessionEvent::Resumed);
+                } else {
+                    self.clear_sent_messages();
+                    self.session_events.push(SessionEvent::New);
+                }
+            }
+            (RepliconClientStatus::Reconnecting { .. }, new)
+                if !matches!(new, RepliconClientStatus::Reconnecting { .. }) =>
+            {
+                for channel_messages in &mut self.received_messages {
+                    channel_messages.clear();
+                }
+                self.clear_sent_messages();
+            }
+            (old, new) if is_connected_status(&old) && !is_connected_status(&new) => {
+                for channel_messages in &mut self.received_messages {
+                    channel_messages.clear();
+                }
+                self.clear_sent_messages();
+            }
+            _ => (),
+        }
+
+        if let RepliconClientStatus::Disconnected { reason } = &status {
+            if !matches!(self.status, RepliconClientStatus::Disconnected { .. }) {
+                self.disconnect_events.push(reason.clone());
             }
-            self.sent_messages.clear();
         }
 
         self.status = status;
     }
 
+    /// Drains buffered [`DisconnectReason`]s produced by [`Self::set_status`].
+    ///
+    /// Should be called only by [`forward_disconnect_events`] to re-emit them as a Bevy event.
+    pub(super) fn drain_disconnect_events(
+        &mut self,
+    ) -> impl Iterator<Item = DisconnectReason> + '_ {
+        self.disconnect_events.drain(..)
+    }
+
+    /// Sets the policy that bounds how many buffered sent messages are retained while
+    /// [`RepliconClientStatus::Reconnecting`].
+    pub fn set_reconnect_policy(&mut self, policy: ReconnectPolicy) {
+        self.reconnect_policy = policy;
+    }
+
+    /// Drops the oldest buffered sent messages until both of the reconnect policy's limits
+    /// are satisfied.
+    ///
+    /// Replenishes a credit for each dropped message's channel (see [`Self::restore_credit`]),
+    /// since a dropped message will never reach the backend either.
+    fn trim_buffered_sent(&mut self) {
+        while self.sent_messages.len() > self.reconnect_policy.max_messages {
+            let (channel_id, _) = self.sent_messages.remove(0);
+            self.restore_credit(channel_id);
+        }
+
+        let mut total_bytes: usize = self
+            .sent_messages
+            .iter()
+            .map(|(_, bytes)| bytes.len())
+            .sum();
+        while total_bytes > self.reconnect_policy.max_bytes && !self.sent_messages.is_empty() {
+            let (channel_id, bytes) = self.sent_messages.remove(0);
+            total_bytes -= bytes.len();
+            self.restore_credit(channel_id);
+        }
+    }
+
+    /// Removes all buffered sent messages, replenishing a credit for each one's channel (see
+    /// [`Self::restore_credit`]), since a cleared message will never reach the backend.
+    fn clear_sent_messages(&mut self) {
+        let channel_ids: Vec<u8> = self
+            .sent_messages
+            .drain(..)
+            .map(|(channel_id, _)| channel_id)
+            .collect();
+        for channel_id in channel_ids {
+            self.restore_credit(channel_id);
+        }
+    }
+
+    /// Replenishes one credit for a channel, if it has a configured budget (see
+    /// [`Self::set_channel_credits`]).
+    fn restore_credit(&mut self, channel_id: u8) {
+        if let Some(credits) = self
+            .channel_credits
+            .get_mut(channel_id as usize)
+            .and_then(Option::as_mut)
+        {
+            *credits = credits.saturating_add(1);
+        }
+    }
+
+    /// Drains buffered [`SessionEvent`]s produced by [`Self::set_status`].
+    ///
+    /// Should be called only by [`forward_session_events`] to re-emit them as a Bevy event.
+    pub(super) fn drain_session_events(&mut self) -> impl Iterator<Item = SessionEvent> + '_ {
+        self.session_events.drain(..)
+    }
+
+    /// Returns `true` if the client is reconnecting.
+    ///
+    /// See also [`Self::status`].
+    #[inline]
+    pub fn is_reconnecting(&self) -> bool {
+        matches!(self.status, RepliconClientStatus::Reconnecting { .. })
+    }
+
     /// Returns the current client status.
     ///
     /// See also [`Self::set_status`].
     #[inline]
     pub fn status(&self) -> RepliconClientStatus {
-        self.status
+        self.status.clone()
     }
 
     /// Returns `true` if the client is disconnected.
@@ -92,7 +314,7 @@ impl RepliconClient {
     /// See also [`Self::status`].
     #[inline]
     pub fn is_disconnected(&self) -> bool {
-        matches!(self.status, RepliconClientStatus::Disconnected)
+        matches!(self.status, RepliconClientStatus::Disconnected { .. })
     }
 
     /// Returns `true` if the client is connecting.
@@ -126,9 +348,28 @@ impl RepliconClient {
 
     /// Removes all sent messages, returning them as an iterator with channel.
     ///
+    /// Replenishes a credit for each message's channel (see [`Self::set_channel_credits`]), since
+    /// the message is now the backend's responsibility rather than sitting in the queue.
+    /// While [`RepliconClientStatus::Reconnecting`], yields nothing and keeps the buffered
+    /// messages queued (see [`Self::set_status`]).
     /// Should be called only from the messaging backend.
     pub fn drain_sent(&mut self) -> impl Iterator<Item = (u8, Bytes)> + '_ {
-        self.sent_messages.drain(..)
+        let end = if self.is_reconnecting() {
+            0
+        } else {
+            self.sent_messages.len()
+        };
+        for &(channel_id, _) in &self.sent_messages[..end] {
+            if let Some(credits) = self
+                .channel_credits
+                .get_mut(channel_id as usize)
+                .and_then(Option::as_mut)
+            {
+                *credits = credits.saturating_add(1);
+            }
+        }
+
+        self.sent_messages.drain(..end)
     }
 
     /// Adds a message from the server to the list of received messages.
@@ -147,20 +388,167 @@ impl RepliconClient {
             .unwrap_or_else(|| panic!("client should have a channel with id {channel_id}"));
 
         channel_messages.push(message.into());
+        self.received_tick = self.received_tick.wrapping_add(1);
+    }
+
+    /// Returns a counter that increments every time a message is received over any channel.
+    ///
+    /// Used to detect connection activity independent of which channel carried the traffic.
+    #[inline]
+    pub fn received_tick(&self) -> u64 {
+        self.received_tick
+    }
+
+    /// Returns currently buffered received messages for each channel without consuming them.
+    ///
+    /// Top index is channel ID. Used by [`diagnostics`](super::diagnostics) to measure per-channel
+    /// traffic before the replication systems pop messages later in the same tick.
+    pub(crate) fn received_messages(&self) -> &[Vec<Bytes>] {
+        &self.received_messages
+    }
+}
+
+/// Registers [`SessionEvent`] and [`DisconnectReason`] as Bevy events and forwards
+/// [`RepliconClient`]'s buffered ones each tick.
+///
+/// Without this plugin, session and disconnect events still buffer internally, but game code has
+/// no way to observe them as events.
+pub struct ClientEventsPlugin;
+
+impl Plugin for ClientEventsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SessionEvent>()
+            .add_event::<DisconnectReason>()
+            .add_systems(
+                PreUpdate,
+                (forward_session_events, forward_disconnect_events)
+                    .in_set(ClientSet::ReceivePackets),
+            );
     }
 }
 
 /// Connection status of the [`RepliconClient`].
-#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum RepliconClientStatus {
     /// Not connected or trying to connect.
-    #[default]
-    Disconnected,
+    Disconnected {
+        /// Why the client is disconnected.
+        reason: DisconnectReason,
+    },
     /// Trying to connect to the server.
     Connecting,
+    /// Lost the connection and attempting to re-establish it.
+    ///
+    /// Stores the client ID from before the drop, if any, so [`RepliconClient::set_status`] can
+    /// tell a resumed session from a fresh one once [`RepliconClientStatus::Connected`] is
+    /// reported again.
+    Reconnecting { client_id: Option<ClientId> },
     /// Connected to the server.
     ///
     /// Stores the assigned ID if one was assigned by the server.
     /// Needed only for users to access ID independent from messaging library.
     Connected { client_id: Option<ClientId> },
 }
+
+impl Default for RepliconClientStatus {
+    fn default() -> Self {
+        Self::Disconnected {
+            reason: DisconnectReason::default(),
+        }
+    }
+}
+
+fn is_connected_status(status: &RepliconClientStatus) -> bool {
+    matches!(status, RepliconClientStatus::Connected { .. })
+}
+
+/// Why a client ended up [`RepliconClientStatus::Disconnected`].
+///
+/// Set by the messaging backend via [`RepliconClient::set_status`]. Read it via
+/// [`EventReader<DisconnectReason>`] to show the user why they were dropped instead of guessing.
+#[derive(Event, Clone, Debug, Default, PartialEq)]
+pub enum DisconnectReason {
+    /// The client or application requested the disconnect.
+    #[default]
+    Requested,
+    /// No traffic was received from the server within the configured timeout.
+    Timeout,
+    /// The messaging backend reported a transport-level error.
+    TransportError,
+    /// The server rejected the connection because it was full.
+    ServerFull,
+    /// The server forcibly disconnected the client, optionally explaining why.
+    Kicked {
+        /// Message sent by the server, if any.
+        message: Option<String>,
+    },
+}
+
+/// Re-emits [`RepliconClient`]'s buffered [`DisconnectReason`]s as a Bevy event.
+///
+/// Should run in [`ClientSet::ReceivePackets`](super::ClientSet::ReceivePackets).
+pub(super) fn forward_disconnect_events(
+    mut client: ResMut<RepliconClient>,
+    mut events: EventWriter<DisconnectReason>,
+) {
+    events.send_batch(client.drain_disconnect_events());
+}
+
+/// Bounds how many buffered sent messages are retained while
+/// [`RepliconClientStatus::Reconnecting`], so a long outage can't grow the buffer without limit.
+#[derive(Clone, Copy, Debug)]
+pub struct ReconnectPolicy {
+    /// Maximum number of buffered messages retained while reconnecting.
+    pub max_messages: usize,
+    /// Maximum total size in bytes of buffered messages retained while reconnecting.
+    pub max_bytes: usize,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            max_messages: 1024,
+            max_bytes: 1024 * 1024,
+        }
+    }
+}
+
+/// Event emitted after a [`RepliconClientStatus::Reconnecting`] client reports
+/// [`RepliconClientStatus::Connected`] again.
+#[derive(Event, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SessionEvent {
+    /// The server resumed the previous session; buffered messages were flushed to the backend.
+    Resumed,
+    /// The server started a fresh session; buffered messages were discarded.
+    New,
+}
+
+/// Re-emits [`RepliconClient`]'s buffered [`SessionEvent`]s as a Bevy event.
+///
+/// Should run in [`ClientSet::ReceivePackets`](super::ClientSet::ReceivePackets).
+pub(super) fn forward_session_events(
+    mut client: ResMut<RepliconClient>,
+    mut events: EventWriter<SessionEvent>,
+) {
+    events.send_batch(client.drain_session_events());
+}
+
+/// Error returned by [`RepliconClient::send`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SendError {
+    /// The channel's credit budget is exhausted.
+    ///
+    /// Retry later, once [`RepliconClient::drain_sent`] replenishes credits for sent messages or
+    /// the backend grants more via [`RepliconClient::grant_channel_credits`].
+    WouldBlock,
+}
+
+impl std::fmt::Display for SendError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::WouldBlock => write!(f, "channel has no send credits left"),
+        }
+    }
+}
+
+impl std::error::Error for SendError {}
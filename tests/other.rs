@@ -1,4 +1,9 @@
-use bevy::prelude::*;
+use std::time::Duration;
+
+use bevy::{
+    ecs::event::{Events, ManualEventReader},
+    prelude::*,
+};
 use bevy_replicon::{
     core::{replicon_channels::ReplicationChannel, replicon_tick::RepliconTick},
     prelude::*,
@@ -47,10 +52,12 @@ fn client_cleanup_on_disconnect() {
     let mut client = app.world.resource_mut::<RepliconClient>();
     client.set_status(RepliconClientStatus::Connected { client_id: None });
 
-    client.send(ReplicationChannel::Init, Vec::new());
+    client.send(ReplicationChannel::Init, Vec::new()).unwrap();
     client.insert_received(ReplicationChannel::Init, Vec::new());
 
-    client.set_status(RepliconClientStatus::Disconnected);
+    client.set_status(RepliconClientStatus::Disconnected {
+        reason: DisconnectReason::Requested,
+    });
 
     assert_eq!(client.drain_sent().count(), 0);
     assert!(client.receive(ReplicationChannel::Init).is_none());
@@ -105,7 +112,7 @@ fn client_disconnected() {
 
     let mut client = app.world.resource_mut::<RepliconClient>();
 
-    client.send(ReplicationChannel::Init, Vec::new());
+    client.send(ReplicationChannel::Init, Vec::new()).unwrap();
     client.insert_received(ReplicationChannel::Init, Vec::new());
 
     assert_eq!(client.drain_sent().count(), 0);
@@ -201,6 +208,277 @@ fn diagnostics() {
     assert_eq!(stats.despawns, 1);
     assert_eq!(stats.packets, 2);
     assert_eq!(stats.bytes, 33);
+
+    let channel_id: u8 = ReplicationChannel::Init.into();
+    let channel_stats = &stats.channels[channel_id as usize];
+    assert_eq!(channel_stats.messages, 2);
+    assert_eq!(channel_stats.bytes, 33);
+}
+
+#[test]
+fn send_credit_replenished_on_discard() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        RepliconPlugins.set(ServerPlugin {
+            tick_policy: TickPolicy::EveryFrame,
+            ..Default::default()
+        }),
+    ));
+
+    app.update();
+
+    let mut client = app.world.resource_mut::<RepliconClient>();
+    client.set_status(RepliconClientStatus::Connected { client_id: None });
+    client.set_channel_credits(ReplicationChannel::Init, 1);
+
+    client.send(ReplicationChannel::Init, Vec::new()).unwrap();
+    assert_eq!(
+        client.send(ReplicationChannel::Init, Vec::new()),
+        Err(SendError::WouldBlock)
+    );
+
+    // Disconnecting while connected discards the buffered message without draining it; the
+    // channel's credit must still come back, or it's gone forever.
+    client.set_status(RepliconClientStatus::Disconnected {
+        reason: DisconnectReason::Requested,
+    });
+    client.set_status(RepliconClientStatus::Connected { client_id: None });
+
+    client.send(ReplicationChannel::Init, Vec::new()).unwrap();
+}
+
+#[test]
+fn trim_buffered_sent_replenishes_credits() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        RepliconPlugins.set(ServerPlugin {
+            tick_policy: TickPolicy::EveryFrame,
+            ..Default::default()
+        }),
+    ));
+
+    app.update();
+
+    let mut client = app.world.resource_mut::<RepliconClient>();
+    client.set_status(RepliconClientStatus::Connected { client_id: None });
+    client.set_channel_credits(ReplicationChannel::Init, 2);
+    client.set_reconnect_policy(ReconnectPolicy {
+        max_messages: 1,
+        max_bytes: 1024,
+    });
+
+    client.send(ReplicationChannel::Init, vec![1]).unwrap();
+    client.send(ReplicationChannel::Init, vec![2]).unwrap();
+    assert_eq!(
+        client.send(ReplicationChannel::Init, vec![3]),
+        Err(SendError::WouldBlock)
+    );
+
+    // Going `Reconnecting` trims the buffer down to the policy's `max_messages` limit; the
+    // trimmed message's credit must come back even though it was dropped, not drained.
+    client.set_status(RepliconClientStatus::Reconnecting { client_id: None });
+
+    // Abandoning the reconnect attempt discards what's left of the buffer; that credit must come
+    // back too.
+    client.set_status(RepliconClientStatus::Disconnected {
+        reason: DisconnectReason::Requested,
+    });
+    client.set_status(RepliconClientStatus::Connected { client_id: None });
+
+    client.send(ReplicationChannel::Init, vec![4]).unwrap();
+    client.send(ReplicationChannel::Init, vec![5]).unwrap();
+    assert_eq!(
+        client.send(ReplicationChannel::Init, vec![6]),
+        Err(SendError::WouldBlock)
+    );
+}
+
+#[test]
+fn new_session_discard_replenishes_credits() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        RepliconPlugins.set(ServerPlugin {
+            tick_policy: TickPolicy::EveryFrame,
+            ..Default::default()
+        }),
+    ));
+
+    app.update();
+
+    let mut client = app.world.resource_mut::<RepliconClient>();
+    client.set_channel_credits(ReplicationChannel::Init, 1);
+    client.set_status(RepliconClientStatus::Connected {
+        client_id: Some(ClientId::new(1)),
+    });
+
+    client.send(ReplicationChannel::Init, vec![1]).unwrap();
+    assert_eq!(
+        client.send(ReplicationChannel::Init, vec![2]),
+        Err(SendError::WouldBlock)
+    );
+
+    client.set_status(RepliconClientStatus::Reconnecting {
+        client_id: Some(ClientId::new(1)),
+    });
+
+    // The server hands back a different client ID on resume: a new session, not a resumed one.
+    // The buffered message is discarded rather than flushed, so its credit must come back.
+    client.set_status(RepliconClientStatus::Connected {
+        client_id: Some(ClientId::new(2)),
+    });
+
+    client.send(ReplicationChannel::Init, vec![3]).unwrap();
+}
+
+#[test]
+fn reconnect_buffers_and_events() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        RepliconPlugins.set(ServerPlugin {
+            tick_policy: TickPolicy::EveryFrame,
+            ..Default::default()
+        }),
+        ClientEventsPlugin,
+    ));
+
+    app.update();
+
+    let client_id = ClientId::new(1);
+    let mut client = app.world.resource_mut::<RepliconClient>();
+    client.set_status(RepliconClientStatus::Connected {
+        client_id: Some(client_id),
+    });
+    client
+        .send(ReplicationChannel::Init, vec![1, 2, 3])
+        .unwrap();
+
+    client.set_status(RepliconClientStatus::Reconnecting {
+        client_id: Some(client_id),
+    });
+    assert_eq!(
+        client.drain_sent().count(),
+        0,
+        "message should be buffered, not flushed, while reconnecting"
+    );
+
+    // A repeated `Reconnecting` report (e.g. the backend updating the known client ID) must not
+    // wipe the buffer it exists to preserve across the outage.
+    client.set_status(RepliconClientStatus::Reconnecting {
+        client_id: Some(client_id),
+    });
+
+    client.set_status(RepliconClientStatus::Connected {
+        client_id: Some(client_id),
+    });
+    assert_eq!(
+        client.drain_sent().count(),
+        1,
+        "buffered message should survive the reconnect"
+    );
+
+    client.set_status(RepliconClientStatus::Disconnected {
+        reason: DisconnectReason::Timeout,
+    });
+
+    app.update();
+
+    let session_events = app.world.resource::<Events<SessionEvent>>();
+    let received: Vec<_> = ManualEventReader::default()
+        .read(session_events)
+        .copied()
+        .collect();
+    assert_eq!(received, vec![SessionEvent::Resumed]);
+
+    let disconnect_events = app.world.resource::<Events<DisconnectReason>>();
+    let received: Vec<_> = ManualEventReader::default()
+        .read(disconnect_events)
+        .cloned()
+        .collect();
+    assert_eq!(received, vec![DisconnectReason::Timeout]);
+}
+
+#[test]
+fn heartbeat_no_false_timeout_on_connect() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        RepliconPlugins.set(ServerPlugin {
+            tick_policy: TickPolicy::EveryFrame,
+            ..Default::default()
+        }),
+        HeartbeatPlugin {
+            config: HeartbeatConfig {
+                interval: Duration::from_millis(10),
+                timeout: Duration::from_millis(50),
+            },
+        },
+    ));
+
+    // Let the app's clock run well past `timeout` before the client ever connects, mirroring a
+    // menu or loading screen.
+    app.update();
+    let mut time = app.world.resource_mut::<Time<Virtual>>();
+    time.advance_by(Duration::from_secs(1));
+
+    let mut client = app.world.resource_mut::<RepliconClient>();
+    client.set_status(RepliconClientStatus::Connected { client_id: None });
+
+    app.update();
+
+    let client = app.world.resource::<RepliconClient>();
+    assert!(
+        client.is_connected(),
+        "a brand-new connection must not be immediately kicked back to Reconnecting just because \
+         the app has been running longer than the heartbeat timeout"
+    );
+}
+
+#[test]
+fn heartbeat_channel_beyond_configured_channels_does_not_panic() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        RepliconPlugins.set(ServerPlugin {
+            tick_policy: TickPolicy::EveryFrame,
+            ..Default::default()
+        }),
+        HeartbeatPlugin {
+            config: HeartbeatConfig {
+                interval: Duration::ZERO,
+                timeout: Duration::from_secs(10),
+            },
+        },
+    ));
+
+    app.update();
+
+    let mut client = app.world.resource_mut::<RepliconClient>();
+    client.set_status(RepliconClientStatus::Connected { client_id: None });
+
+    // The real server channel registry only configures a handful of channels, far fewer than
+    // `HEARTBEAT_CHANNEL_ID` (255); sending and receiving on it must not panic as an
+    // out-of-bounds channel.
+    app.update();
+
+    let mut client = app.world.resource_mut::<RepliconClient>();
+    let payload = client
+        .drain_sent()
+        .find(|&(channel_id, _)| channel_id == HEARTBEAT_CHANNEL_ID)
+        .map(|(_, payload)| payload)
+        .expect("a heartbeat should have been sent this tick");
+    client.insert_received(HEARTBEAT_CHANNEL_ID, payload);
+
+    app.update();
+
+    let stats = app.world.resource::<NetworkStats>();
+    assert!(
+        stats.rtt().is_some(),
+        "echoing the heartbeat payload back should have been measured as an RTT sample"
+    );
 }
 
 #[derive(Component, Deserialize, Serialize)]